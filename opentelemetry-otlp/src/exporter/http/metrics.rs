@@ -1,13 +1,19 @@
+use std::io::Write;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use http::{header::CONTENT_TYPE, Method};
+use http::{
+    header::{CONTENT_ENCODING, CONTENT_TYPE, RETRY_AFTER},
+    Method, StatusCode,
+};
 use opentelemetry::metrics::{MetricsError, Result};
 use opentelemetry_sdk::metrics::data::ResourceMetrics;
+use rand::Rng;
 
 use crate::{metric::MetricsClient, Error};
 
-use super::OtlpHttpClient;
+use super::{Compression, OtlpHttpClient, Protocol, RetryPolicy};
 
 #[async_trait]
 impl MetricsClient for OtlpHttpClient {
@@ -21,35 +27,107 @@ impl MetricsClient for OtlpHttpClient {
                 _ => Err(MetricsError::Other("exporter is already shut down".into())),
             })?;
 
-        let (body, content_type) = build_body(metrics)?;
-        let mut request = http::Request::builder()
-            .method(Method::POST)
-            .uri(&self.collector_endpoint)
-            .header(CONTENT_TYPE, content_type)
-            .body(body)
-            .map_err(|e| crate::Error::RequestFailed(Box::new(e)))?;
+        let (body, content_type) = build_body(self.protocol, metrics)?;
+        let body = match self.compression {
+            Compression::None => body,
+            Compression::Gzip => compress_gzip(&body)?,
+        };
 
-        for (k, v) in &self.headers {
-            request.headers_mut().insert(k.clone(), v.clone());
-        }
+        let started_at = Instant::now();
+        let mut attempt: u32 = 0;
+        let response = loop {
+            attempt += 1;
+
+            let mut request = http::Request::builder()
+                .method(Method::POST)
+                .uri(&self.collector_endpoint)
+                .header(CONTENT_TYPE, content_type)
+                .body(body.clone())
+                .map_err(|e| crate::Error::RequestFailed(Box::new(e)))?;
+
+            if self.compression == Compression::Gzip {
+                request
+                    .headers_mut()
+                    .insert(CONTENT_ENCODING, http::HeaderValue::from_static("gzip"));
+            }
+
+            for (k, v) in &self.headers {
+                request.headers_mut().insert(k.clone(), v.clone());
+            }
+
+            if let Some(header_provider) = &self.header_provider {
+                // `HeaderMap`'s by-value iterator only yields `Some(name)` for
+                // the first value of a repeated header; later values for that
+                // same name come back as `None` and reuse the last name seen.
+                let mut replaced = std::collections::HashSet::new();
+                let mut last_name: Option<http::HeaderName> = None;
+                for (k, v) in header_provider() {
+                    let name = match k {
+                        Some(k) => {
+                            last_name = Some(k.clone());
+                            k
+                        }
+                        None => last_name
+                            .clone()
+                            .expect("HeaderMap yields a name before its first value"),
+                    };
+
+                    // Clear any static value(s) under this name the first time
+                    // the provider supplies it, so the provider always wins.
+                    if replaced.insert(name.clone()) {
+                        request.headers_mut().remove(&name);
+                    }
+                    request.headers_mut().append(name, v);
+                }
+            }
 
-        let response = client
-            .send(request)
-            .await
-            .map_err(|e| MetricsError::ExportErr(Box::new(Error::RequestFailed(e))))?;
-
-        // TODO: use `opentelemetry_http::ResponseExt` instead (currently it returns TraceError)
-        if !response.status().is_success() {
-            let body_msg = std::str::from_utf8(response.body().iter().as_slice())
-                .unwrap_or("response body cannot be decoded");
-            return Err(MetricsError::ExportErr(Box::new(Error::RequestFailed(
-                format!(
-                    "request failed with status {} (Body: {})",
-                    response.status(),
-                    body_msg
-                )
-                .into(),
-            ))))?;
+            // TODO: use `opentelemetry_http::ResponseExt` instead (currently it returns TraceError)
+            match client.send(request).await {
+                Ok(response) if response.status().is_success() => break response,
+                Ok(response) if is_retriable_status(response.status()) => {
+                    match next_retry_delay(&self.retry_policy, attempt, started_at, &response) {
+                        Some(delay) => {
+                            tokio::time::sleep(delay).await;
+                            continue;
+                        }
+                        None => return Err(status_error(&response)),
+                    }
+                }
+                Ok(response) => return Err(status_error(&response)),
+                Err(e) => {
+                    match next_retry_delay_for_connection_error(
+                        &self.retry_policy,
+                        attempt,
+                        started_at,
+                    ) {
+                        Some(delay) => {
+                            tokio::time::sleep(delay).await;
+                            continue;
+                        }
+                        None => {
+                            return Err(MetricsError::ExportErr(Box::new(Error::RequestFailed(e))))
+                        }
+                    }
+                }
+            }
+        };
+
+        match partial_success_from_response(self.protocol, response.body()) {
+            Ok(Some(partial_success))
+                if partial_success.rejected_data_points > 0
+                    || !partial_success.error_message.is_empty() =>
+            {
+                opentelemetry::global::handle_error(MetricsError::Other(format!(
+                    "partial success: {} data point(s) rejected, error message: {}",
+                    partial_success.rejected_data_points, partial_success.error_message
+                )));
+            }
+            Ok(_) => {}
+            // The collector accepted the batch; a response body we can't decode
+            // doesn't undo that, so log it rather than failing the export.
+            Err(e) => opentelemetry::global::handle_error(MetricsError::Other(format!(
+                "failed to decode the export response body: {e}"
+            ))),
         }
 
         Ok(())
@@ -62,25 +140,197 @@ impl MetricsClient for OtlpHttpClient {
     }
 }
 
-#[cfg(feature = "http-proto")]
-fn build_body(metrics: &mut ResourceMetrics) -> Result<(Vec<u8>, &'static str)> {
-    use prost::Message;
-
+fn build_body(
+    protocol: Protocol,
+    metrics: &mut ResourceMetrics,
+) -> Result<(Vec<u8>, &'static str)> {
     let req: opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest =
         (&*metrics).into();
-    let mut buf = vec![];
-    req.encode(&mut buf).map_err(crate::Error::from)?;
 
-    Ok((buf, "application/x-protobuf"))
+    match protocol {
+        #[cfg(feature = "http-proto")]
+        Protocol::HttpBinary => {
+            use prost::Message;
+
+            let mut buf = vec![];
+            req.encode(&mut buf).map_err(crate::Error::from)?;
+
+            Ok((buf, "application/x-protobuf"))
+        }
+        #[cfg(feature = "http-json")]
+        Protocol::HttpJson => {
+            let buf = serde_json::to_vec(&req)
+                .map_err(|e| crate::Error::RequestFailed(Box::new(e)))?;
+
+            Ok((buf, "application/json"))
+        }
+        #[allow(unreachable_patterns)]
+        _ => Err(MetricsError::Other(
+            "No http protocol configured. Enable one via `http-proto` or `http-json`".into(),
+        )),
+    }
+}
+
+
+fn status_error(response: &http::Response<opentelemetry_http::Bytes>) -> MetricsError {
+    let body_msg = std::str::from_utf8(response.body().iter().as_slice())
+        .unwrap_or("response body cannot be decoded");
+
+    MetricsError::ExportErr(Box::new(Error::RequestFailed(
+        format!(
+            "request failed with status {} (Body: {})",
+            response.status(),
+            body_msg
+        )
+        .into(),
+    )))
+}
+
+fn is_retriable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::REQUEST_TIMEOUT
+            | StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Returns the time budget left for retries, or `None` once the retry
+/// policy's attempt/elapsed-time budget is exhausted.
+fn remaining_retry_budget(
+    retry_policy: &RetryPolicy,
+    attempt: u32,
+    started_at: Instant,
+) -> Option<Duration> {
+    let remaining = retry_policy
+        .max_elapsed_time
+        .saturating_sub(started_at.elapsed());
+    if attempt > retry_policy.max_retries || remaining.is_zero() {
+        return None;
+    }
+
+    Some(remaining)
+}
+
+/// Computes the delay before the next attempt after a non-2xx response, or
+/// `None` once the retry policy's attempt/elapsed-time budget is exhausted.
+///
+/// A `Retry-After` header on the response takes precedence over the
+/// computed backoff, per the HTTP spec. Either way, the delay is clamped to
+/// what's left of `max_elapsed_time` so a large `Retry-After` can't push the
+/// exporter past its configured budget.
+fn next_retry_delay(
+    retry_policy: &RetryPolicy,
+    attempt: u32,
+    started_at: Instant,
+    response: &http::Response<opentelemetry_http::Bytes>,
+) -> Option<Duration> {
+    let remaining = remaining_retry_budget(retry_policy, attempt, started_at)?;
+    let delay = retry_after_delay(response).unwrap_or_else(|| backoff_delay(retry_policy, attempt));
+
+    Some(delay.min(remaining))
+}
+
+/// Computes the delay before the next attempt after a connection error (no
+/// response to read a `Retry-After` header from), or `None` once the retry
+/// policy's attempt/elapsed-time budget is exhausted.
+fn next_retry_delay_for_connection_error(
+    retry_policy: &RetryPolicy,
+    attempt: u32,
+    started_at: Instant,
+) -> Option<Duration> {
+    let remaining = remaining_retry_budget(retry_policy, attempt, started_at)?;
+
+    Some(backoff_delay(retry_policy, attempt).min(remaining))
+}
+
+/// Exponential backoff capped at `max_backoff`, with jitter applied as a
+/// random factor in `[0.5, 1.0]` of the computed delay (half jitter).
+fn backoff_delay(retry_policy: &RetryPolicy, attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1) as i32;
+    let backoff = retry_policy
+        .initial_backoff
+        .mul_f64(retry_policy.backoff_multiplier.powi(exponent))
+        .min(retry_policy.max_backoff);
+
+    backoff.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+}
+
+/// Parses a `Retry-After` header in either delay-seconds or HTTP-date form.
+fn retry_after_delay(response: &http::Response<opentelemetry_http::Bytes>) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let target = httpdate::parse_http_date(value).ok()?;
+    Some(
+        target
+            .duration_since(std::time::SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// The `partial_success` field of an `ExportMetricsServiceResponse`, surfaced
+/// when the collector accepted the request but rejected some data points.
+struct PartialSuccess {
+    rejected_data_points: i64,
+    error_message: String,
 }
 
-#[cfg(not(feature = "http-proto"))]
-fn build_body(metrics: &mut ResourceMetrics) -> Result<(Vec<u8>, &'static str)> {
-    Err(MetricsError::Other(
-        "No http protocol configured. Enable one via `http-proto`".into(),
-    ))
+/// Decodes the response body, if any, and returns its `partial_success`
+/// field when the collector reported one.
+///
+/// An empty body (e.g. from a collector that predates partial-success
+/// support) is treated as a clean export, not an error.
+fn partial_success_from_response(
+    protocol: Protocol,
+    body: &[u8],
+) -> Result<Option<PartialSuccess>> {
+    use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceResponse;
+
+    if body.is_empty() {
+        return Ok(None);
+    }
+
+    let response: ExportMetricsServiceResponse = match protocol {
+        #[cfg(feature = "http-proto")]
+        Protocol::HttpBinary => {
+            use prost::Message;
+
+            Message::decode(body).map_err(crate::Error::from)?
+        }
+        #[cfg(feature = "http-json")]
+        Protocol::HttpJson => {
+            serde_json::from_slice(body).map_err(|e| crate::Error::RequestFailed(Box::new(e)))?
+        }
+        #[allow(unreachable_patterns)]
+        _ => return Ok(None),
+    };
+
+    Ok(response.partial_success.map(|partial_success| PartialSuccess {
+        rejected_data_points: partial_success.rejected_data_points,
+        error_message: partial_success.error_message,
+    }))
 }
 
+fn compress_gzip(body: &[u8]) -> Result<Vec<u8>> {
+    use flate2::{write::GzEncoder, Compression as GzipCompression};
+
+    let mut encoder = GzEncoder::new(Vec::new(), GzipCompression::default());
+    encoder
+        .write_all(body)
+        .map_err(|e| crate::Error::RequestFailed(Box::new(e)))?;
+    let body = encoder
+        .finish()
+        .map_err(|e| crate::Error::RequestFailed(Box::new(e)))?;
+
+    Ok(body)
+}
 
 #[cfg(test)]
 mod tests {
@@ -92,6 +342,9 @@ mod tests {
         Resource,
     };
 
+    #[cfg(feature = "http-json")]
+    use super::{build_body, Protocol};
+
     #[derive(Debug, Default)]
     struct MockClient {
         response_bytes: Bytes,
@@ -108,6 +361,23 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "http-json")]
+    #[test]
+    fn test_build_body_http_json() {
+        use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+
+        let mut metrics = ResourceMetrics {
+            resource: Resource::default(),
+            scope_metrics: vec![],
+        };
+
+        let (body, content_type) = build_body(Protocol::HttpJson, &mut metrics).unwrap();
+
+        assert_eq!(content_type, "application/json");
+        let decoded: ExportMetricsServiceRequest = serde_json::from_slice(&body).unwrap();
+        assert_eq!(decoded, (&metrics).into());
+    }
+
     #[tokio::test]
     async fn test_bad_status_code_error_message() {
         let client = MockClient {
@@ -131,4 +401,295 @@ mod tests {
         assert!(debug_err.contains("400"));
         assert!(debug_err.contains("Details"));
     }
+
+    /// Records the last request it received.
+    #[derive(Debug, Default)]
+    struct CapturingClient {
+        last_request: std::sync::Mutex<Option<Request<Vec<u8>>>>,
+    }
+
+    #[async_trait]
+    impl HttpClient for CapturingClient {
+        async fn send(&self, request: Request<Vec<u8>>) -> Result<Response<Bytes>, HttpError> {
+            *self.last_request.lock().unwrap() = Some(request);
+
+            Ok(Response::<Bytes>::new(Bytes::new()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gzip_compression() {
+        use flate2::read::GzDecoder;
+        use std::io::Read;
+
+        let client = std::sync::Arc::new(CapturingClient::default());
+        let exporter = opentelemetry_otlp::new_exporter()
+            .http()
+            .with_http_client(std::sync::Arc::clone(&client))
+            .with_compression(super::Compression::Gzip)
+            .build_metrics_exporter(
+                Box::new(opentelemetry_sdk::metrics::reader::DefaultAggregationSelector::new()),
+                Box::new(opentelemetry_sdk::metrics::reader::DefaultTemporalitySelector::new()),
+            )
+            .unwrap();
+        let mut metrics = ResourceMetrics {
+            resource: Resource::default(),
+            scope_metrics: vec![],
+        };
+
+        exporter.export(&mut metrics).await.unwrap();
+
+        let request = client.last_request.lock().unwrap().take().unwrap();
+        assert_eq!(
+            request.headers().get(http::header::CONTENT_ENCODING).unwrap(),
+            "gzip"
+        );
+
+        let mut decompressed = vec![];
+        GzDecoder::new(request.body().as_slice())
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert!(!decompressed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_partial_success_is_not_an_error() {
+        use opentelemetry_proto::tonic::collector::metrics::v1::{
+            ExportMetricsPartialSuccess, ExportMetricsServiceResponse,
+        };
+        use prost::Message;
+
+        let response = ExportMetricsServiceResponse {
+            partial_success: Some(ExportMetricsPartialSuccess {
+                rejected_data_points: 7,
+                error_message: "some data points were rejected".into(),
+            }),
+        };
+        let mut response_bytes = vec![];
+        response.encode(&mut response_bytes).unwrap();
+
+        let client = MockClient {
+            response_bytes: Bytes::from(response_bytes),
+            status_code: http::StatusCode::OK,
+        };
+        let exporter = opentelemetry_otlp::new_exporter()
+            .http()
+            .with_http_client(client)
+            .build_metrics_exporter(
+                Box::new(opentelemetry_sdk::metrics::reader::DefaultAggregationSelector::new()),
+                Box::new(opentelemetry_sdk::metrics::reader::DefaultTemporalitySelector::new()),
+            )
+            .unwrap();
+        let mut metrics = ResourceMetrics {
+            resource: Resource::default(),
+            scope_metrics: vec![],
+        };
+
+        // A partial success is reported (see global error handler output) but
+        // does not fail the export: the collector did accept the batch.
+        exporter.export(&mut metrics).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_undecodable_success_body_is_not_an_error() {
+        let client = MockClient {
+            response_bytes: Bytes::from("not a valid ExportMetricsServiceResponse"),
+            status_code: http::StatusCode::OK,
+        };
+        let exporter = opentelemetry_otlp::new_exporter()
+            .http()
+            .with_http_client(client)
+            .build_metrics_exporter(
+                Box::new(opentelemetry_sdk::metrics::reader::DefaultAggregationSelector::new()),
+                Box::new(opentelemetry_sdk::metrics::reader::DefaultTemporalitySelector::new()),
+            )
+            .unwrap();
+        let mut metrics = ResourceMetrics {
+            resource: Resource::default(),
+            scope_metrics: vec![],
+        };
+
+        // The collector already accepted the batch with a 200; a body we
+        // can't decode is logged, not turned into an export failure.
+        exporter.export(&mut metrics).await.unwrap();
+    }
+
+    /// Returns `SERVICE_UNAVAILABLE` for the first `failures_before_success`
+    /// requests, then `OK`.
+    #[derive(Debug, Default)]
+    struct FlakyClient {
+        failures_before_success: usize,
+        attempts: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl HttpClient for FlakyClient {
+        async fn send(&self, _: Request<Vec<u8>>) -> Result<Response<Bytes>, HttpError> {
+            let attempt = self
+                .attempts
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let status_code = if attempt < self.failures_before_success {
+                http::StatusCode::SERVICE_UNAVAILABLE
+            } else {
+                http::StatusCode::OK
+            };
+
+            let response = Response::<Bytes>::new(Bytes::new());
+            let (mut parts, body) = response.into_parts();
+            parts.status = status_code;
+            Ok(Response::from_parts(parts, body))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_failures_then_succeeds() {
+        let client = FlakyClient {
+            failures_before_success: 2,
+            ..Default::default()
+        };
+        let exporter = opentelemetry_otlp::new_exporter()
+            .http()
+            .with_http_client(client)
+            .build_metrics_exporter(
+                Box::new(opentelemetry_sdk::metrics::reader::DefaultAggregationSelector::new()),
+                Box::new(opentelemetry_sdk::metrics::reader::DefaultTemporalitySelector::new()),
+            )
+            .unwrap();
+        let mut metrics = ResourceMetrics {
+            resource: Resource::default(),
+            scope_metrics: vec![],
+        };
+
+        exporter.export(&mut metrics).await.unwrap();
+    }
+
+    /// Fails the first `failures_before_success` sends with a connection
+    /// error (as opposed to `FlakyClient`'s bad status codes), then `OK`.
+    #[derive(Debug, Default)]
+    struct FlakyConnectionClient {
+        failures_before_success: usize,
+        attempts: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl HttpClient for FlakyConnectionClient {
+        async fn send(&self, _: Request<Vec<u8>>) -> Result<Response<Bytes>, HttpError> {
+            let attempt = self
+                .attempts
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if attempt < self.failures_before_success {
+                return Err("connection refused".into());
+            }
+
+            Ok(Response::<Bytes>::new(Bytes::new()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_connection_errors_then_succeeds() {
+        let client = FlakyConnectionClient {
+            failures_before_success: 2,
+            ..Default::default()
+        };
+        let exporter = opentelemetry_otlp::new_exporter()
+            .http()
+            .with_http_client(client)
+            .build_metrics_exporter(
+                Box::new(opentelemetry_sdk::metrics::reader::DefaultAggregationSelector::new()),
+                Box::new(opentelemetry_sdk::metrics::reader::DefaultTemporalitySelector::new()),
+            )
+            .unwrap();
+        let mut metrics = ResourceMetrics {
+            resource: Resource::default(),
+            scope_metrics: vec![],
+        };
+
+        exporter.export(&mut metrics).await.unwrap();
+    }
+
+    /// Asserts that every request it receives carries the expected
+    /// `authorization` header.
+    #[derive(Debug, Default)]
+    struct RecordingClient;
+
+    #[async_trait]
+    impl HttpClient for RecordingClient {
+        async fn send(&self, request: Request<Vec<u8>>) -> Result<Response<Bytes>, HttpError> {
+            assert_eq!(
+                request.headers().get(http::header::AUTHORIZATION).unwrap(),
+                "Bearer fresh-token"
+            );
+
+            Ok(Response::<Bytes>::new(Bytes::new()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_header_provider_overrides_static_headers() {
+        let exporter = opentelemetry_otlp::new_exporter()
+            .http()
+            .with_http_client(RecordingClient)
+            .with_headers(std::collections::HashMap::from([(
+                "authorization".to_string(),
+                "Bearer stale-token".to_string(),
+            )]))
+            .with_header_provider(|| {
+                let mut headers = http::HeaderMap::new();
+                headers.insert(
+                    http::header::AUTHORIZATION,
+                    http::HeaderValue::from_static("Bearer fresh-token"),
+                );
+                headers
+            })
+            .build_metrics_exporter(
+                Box::new(opentelemetry_sdk::metrics::reader::DefaultAggregationSelector::new()),
+                Box::new(opentelemetry_sdk::metrics::reader::DefaultTemporalitySelector::new()),
+            )
+            .unwrap();
+        let mut metrics = ResourceMetrics {
+            resource: Resource::default(),
+            scope_metrics: vec![],
+        };
+
+        exporter.export(&mut metrics).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_header_provider_repeated_header_values_survive() {
+        let client = std::sync::Arc::new(CapturingClient::default());
+        let exporter = opentelemetry_otlp::new_exporter()
+            .http()
+            .with_http_client(std::sync::Arc::clone(&client))
+            .with_header_provider(|| {
+                let mut headers = http::HeaderMap::new();
+                headers.append(
+                    http::header::HeaderName::from_static("baggage"),
+                    http::HeaderValue::from_static("a=1"),
+                );
+                headers.append(
+                    http::header::HeaderName::from_static("baggage"),
+                    http::HeaderValue::from_static("b=2"),
+                );
+                headers
+            })
+            .build_metrics_exporter(
+                Box::new(opentelemetry_sdk::metrics::reader::DefaultAggregationSelector::new()),
+                Box::new(opentelemetry_sdk::metrics::reader::DefaultTemporalitySelector::new()),
+            )
+            .unwrap();
+        let mut metrics = ResourceMetrics {
+            resource: Resource::default(),
+            scope_metrics: vec![],
+        };
+
+        exporter.export(&mut metrics).await.unwrap();
+
+        let request = client.last_request.lock().unwrap().take().unwrap();
+        let baggage_values: Vec<_> = request
+            .headers()
+            .get_all(http::header::HeaderName::from_static("baggage"))
+            .iter()
+            .collect();
+        assert_eq!(baggage_values, vec!["a=1", "b=2"]);
+    }
 }